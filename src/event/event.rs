@@ -0,0 +1,26 @@
+use crate::{Interests, Token};
+
+/// A readiness event for a single registered handle, produced by
+/// [`Poll::poll`](crate::Poll::poll).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    token: Token,
+    interests: Interests,
+}
+
+impl Event {
+    pub(crate) fn new(token: Token, interests: Interests) -> Event {
+        Event { token, interests }
+    }
+
+    /// The token supplied when the handle that produced this event was
+    /// registered.
+    pub fn token(&self) -> Token {
+        self.token
+    }
+
+    /// The readiness this event reports.
+    pub fn interests(&self) -> Interests {
+        self.interests
+    }
+}
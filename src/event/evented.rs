@@ -33,6 +33,14 @@ use std::ops::Deref;
 ///
 /// [deregistered]: crate::Registry::deregister
 ///
+/// Types that would rather not track a `Registry` until drop time can be
+/// registered through [`Registry::register_owned`] instead, which returns a
+/// [`Registered`] handle that deregisters itself automatically when
+/// dropped.
+///
+/// [`Registry::register_owned`]: crate::Registry::register_owned
+/// [`Registered`]: crate::event::Registered
+///
 /// # Examples
 ///
 /// Implementing `Evented` on a struct containing a socket:
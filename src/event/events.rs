@@ -0,0 +1,34 @@
+use crate::event::Event;
+
+/// A collection of [`Event`]s, populated by [`Poll::poll`](crate::Poll::poll).
+pub struct Events {
+    inner: Vec<Event>,
+}
+
+impl Events {
+    /// Create a new `Events` with capacity for `capacity` events without
+    /// reallocating.
+    pub fn with_capacity(capacity: usize) -> Events {
+        Events {
+            inner: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// The number of events this `Events` can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Iterate over the events from the most recent `Poll::poll` call.
+    pub fn iter(&self) -> std::slice::Iter<'_, Event> {
+        self.inner.iter()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    pub(crate) fn push(&mut self, event: Event) {
+        self.inner.push(event);
+    }
+}
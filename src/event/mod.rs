@@ -0,0 +1,16 @@
+//! Readiness event types.
+
+mod event;
+mod events;
+mod evented;
+mod registered;
+mod registration;
+mod source;
+
+pub use self::event::Event;
+pub use self::events::Events;
+pub use self::evented::Evented;
+pub use self::registered::{OwnedError, Registered};
+pub use self::registration::{Registration, SetReadiness};
+pub(crate) use self::registration::ReadinessQueue;
+pub use self::source::{Dispatcher, EventSource};
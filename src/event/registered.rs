@@ -0,0 +1,138 @@
+use crate::event::Evented;
+use crate::{Interests, Registry, Token};
+
+use std::fmt;
+use std::io;
+use std::ops::{Deref, DerefMut};
+
+/// An owning handle to a registered [`Evented`] value that deregisters
+/// itself automatically when dropped.
+///
+/// Every `Evented` type, unless otherwise specified, [must be
+/// deregistered] before being dropped or it leaks resources, which means
+/// the caller has to keep a [`Registry`] around until drop time. `Registered`
+/// removes that footgun by keeping its own clone of the registry it was
+/// created with — cheap, since [`Registry`] is itself just an `Arc` handle —
+/// so it can deregister itself in its `Drop` impl without the caller
+/// threading anything through.
+///
+/// Created with [`Registry::register_owned`]. Derefs to the wrapped value,
+/// so the underlying `Evented` can still be used directly.
+///
+/// [must be deregistered]: crate::event::Evented#dropping-evented-types
+pub struct Registered<E: Evented> {
+    registry: Registry,
+    token: Token,
+    inner: Option<E>,
+}
+
+impl<E: Evented> Registered<E> {
+    fn new(
+        registry: Registry,
+        inner: E,
+        token: Token,
+        interests: Interests,
+    ) -> Result<Registered<E>, OwnedError<E>> {
+        match inner.register(&registry, token, interests) {
+            Ok(()) => Ok(Registered {
+                registry,
+                token,
+                inner: Some(inner),
+            }),
+            Err(error) => Err(OwnedError { value: inner, error }),
+        }
+    }
+
+    /// Re-register the wrapped value for `interests`, using the token and
+    /// registry it was created with.
+    pub fn reregister(&self, interests: Interests) -> io::Result<()> {
+        self.inner
+            .as_ref()
+            .expect("Registered value already deregistered")
+            .reregister(&self.registry, self.token, interests)
+    }
+
+    /// Deregister the wrapped value and reclaim it, bypassing the
+    /// automatic deregistration that would otherwise happen on drop.
+    ///
+    /// This intentionally returns `Result<E, OwnedError<E>>` rather than
+    /// the plain `io::Result<E>` one might expect: if the underlying
+    /// `deregister` call fails, a plain `io::Result<E>` has nowhere to put
+    /// `E`, so it would be silently dropped right when the caller asked to
+    /// reclaim it. [`OwnedError::value`] hands it back alongside
+    /// [`OwnedError::error`] so a failed deregistration never loses the
+    /// value.
+    pub fn deregister(mut self) -> Result<E, OwnedError<E>> {
+        let inner = self.inner.take().expect("Registered value already deregistered");
+        match inner.deregister(&self.registry) {
+            Ok(()) => Ok(inner),
+            Err(error) => Err(OwnedError { value: inner, error }),
+        }
+    }
+}
+
+/// Error returned by [`Registry::register_owned`] and
+/// [`Registered::deregister`] on failure, carrying the value back so it
+/// isn't silently dropped along with the error.
+pub struct OwnedError<E> {
+    /// The value that failed to (de)register.
+    pub value: E,
+    /// The underlying I/O error.
+    pub error: io::Error,
+}
+
+impl<E> fmt::Debug for OwnedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedError").field("error", &self.error).finish()
+    }
+}
+
+impl<E> fmt::Display for OwnedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<E> std::error::Error for OwnedError<E> {}
+
+impl<E: Evented> Deref for Registered<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        self.inner.as_ref().expect("Registered value already deregistered")
+    }
+}
+
+impl<E: Evented> DerefMut for Registered<E> {
+    fn deref_mut(&mut self) -> &mut E {
+        self.inner.as_mut().expect("Registered value already deregistered")
+    }
+}
+
+impl<E: Evented> Drop for Registered<E> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            let _ = inner.deregister(&self.registry);
+        }
+    }
+}
+
+impl Registry {
+    /// Register `inner` with this registry and return an owning
+    /// [`Registered`] handle that deregisters it automatically when
+    /// dropped.
+    ///
+    /// `Registered` keeps its own clone of this registry to use at drop
+    /// time. That clone is just a cheap, infallible `Arc` bump — not a
+    /// `try_clone`-style duplication of any underlying system resource — so
+    /// unlike registering a handle directly, this never needs to fail on
+    /// account of the registry handle itself.
+    pub fn register_owned<E: Evented>(
+        &self,
+        inner: E,
+        token: Token,
+        interests: Interests,
+    ) -> Result<Registered<E>, OwnedError<E>> {
+        Registered::new(self.clone(), inner, token, interests)
+    }
+}
@@ -0,0 +1,448 @@
+use crate::event::Evented;
+use crate::{Interests, Registry, Token};
+
+use std::fmt;
+use std::io;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A handle to a user-space [`Evented`] registration.
+///
+/// `Registration` is paired with a [`SetReadiness`] and allows readiness
+/// events to be delivered through a [`Poll`] instance without a backing
+/// system handle. This is useful for bridging in-process signals (e.g. a
+/// worker thread finishing a job) into the same event loop that drives
+/// socket I/O.
+///
+/// A `Registration` is created with [`Registration::new2`], which returns
+/// the `Registration` together with the [`SetReadiness`] used to notify it.
+/// Like any other `Evented` value it must be registered with a [`Registry`]
+/// before readiness changes are observed by [`Poll`].
+///
+/// [`Poll`]: crate::Poll
+/// [`Registry`]: crate::Registry
+///
+/// # Examples
+///
+/// ```
+/// use mio::{Interests, Token};
+/// use mio::event::Registration;
+///
+/// let (registration, set_readiness) = Registration::new2();
+///
+/// // `registry.register(&registration, Token(0), Interests::READABLE)?;`
+/// set_readiness.set_readiness(Interests::READABLE).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Registration {
+    inner: Arc<ReadinessNode>,
+}
+
+/// Notifies a paired [`Registration`] of readiness events.
+///
+/// `SetReadiness` is `Send + Sync + Clone`, so it may be handed to any
+/// number of threads that need to wake up a [`Poll`] loop. Calling
+/// [`SetReadiness::set_readiness`] is cheap: it never waits for the `Poll`,
+/// only briefly holds internal locks and, once the readiness has been
+/// recorded, enqueues the node on the owning registry's
+/// readiness queue at most once per pending change.
+///
+/// [`Poll`]: crate::Poll
+#[derive(Debug, Clone)]
+pub struct SetReadiness {
+    inner: Arc<ReadinessNode>,
+}
+
+impl Registration {
+    /// Create a new `Registration` and paired `SetReadiness`.
+    ///
+    /// See the [`Registration`] documentation for more detail.
+    pub fn new2() -> (Registration, SetReadiness) {
+        let inner = Arc::new(ReadinessNode {
+            next_readiness: AtomicPtr::new(ptr::null_mut()),
+            queued: AtomicBool::new(false),
+            alive: AtomicBool::new(true),
+            state: Mutex::new(None),
+        });
+
+        (
+            Registration {
+                inner: inner.clone(),
+            },
+            SetReadiness { inner },
+        )
+    }
+}
+
+impl Evented for Registration {
+    fn register(
+        &self,
+        registry: &Registry,
+        token: Token,
+        _interests: Interests,
+    ) -> io::Result<()> {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "registration is already registered",
+            ));
+        }
+
+        *state = Some(RegistrationState {
+            queue: registry.readiness_queue(),
+            token,
+            readiness: None,
+        });
+        Ok(())
+    }
+
+    fn reregister(
+        &self,
+        registry: &Registry,
+        token: Token,
+        _interests: Interests,
+    ) -> io::Result<()> {
+        let mut state = self.inner.state.lock().unwrap();
+        match &mut *state {
+            Some(state) => {
+                state.queue = registry.readiness_queue();
+                state.token = token;
+                Ok(())
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "registration is not registered",
+            )),
+        }
+    }
+
+    fn deregister(&self, _registry: &Registry) -> io::Result<()> {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.take().is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "registration is not registered",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        // Mark the node dead so that any entry still sitting on a readiness
+        // queue is skipped instead of producing an event for a freed token.
+        self.inner.alive.store(false, Ordering::Release);
+    }
+}
+
+impl SetReadiness {
+    /// Set the readiness of the paired [`Registration`].
+    ///
+    /// The given `readiness` is merged with any readiness that has not yet
+    /// been observed by a [`Poll`]. If the node is not already queued for
+    /// delivery it is pushed onto the owning registry's readiness queue and
+    /// the registry's waker is notified so a blocked `poll` call returns.
+    ///
+    /// [`Poll`]: crate::Poll
+    pub fn set_readiness(&self, readiness: Interests) -> io::Result<()> {
+        let queue = {
+            let mut state = self.inner.state.lock().unwrap();
+            match &mut *state {
+                Some(state) => {
+                    state.readiness = Some(match state.readiness {
+                        Some(existing) => existing | readiness,
+                        None => readiness,
+                    });
+                    state.queue.clone()
+                }
+                // Not registered yet: record nothing to deliver, there is no
+                // queue to push onto.
+                None => return Ok(()),
+            }
+        };
+
+        // A node must be enqueued at most once per pending readiness change.
+        if self
+            .inner
+            .queued
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            queue.enqueue(self.inner.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Clear any readiness recorded but not yet observed by a [`Poll`].
+    ///
+    /// Used by sources built on top of `Registration` (e.g.
+    /// [`channel`](crate::channel)) that need to drop back to a
+    /// not-ready state once their own backing queue has drained, without
+    /// disturbing whether the node is currently queued for delivery.
+    pub(crate) fn clear_readiness(&self) {
+        let mut state = self.inner.state.lock().unwrap();
+        if let Some(state) = &mut *state {
+            state.readiness = None;
+        }
+    }
+}
+
+/// The mutable, registry-facing half of a [`ReadinessNode`].
+///
+/// Protected by a `Mutex`: it is changed by `register`, `reregister`,
+/// `deregister`, `set_readiness` and `clear_readiness`, and
+/// `ReadinessQueue::drain` takes the pending readiness out of it.
+#[derive(Debug)]
+struct RegistrationState {
+    queue: ReadinessQueue,
+    token: Token,
+    readiness: Option<Interests>,
+}
+
+/// A single entry in a registry's readiness queue.
+///
+/// Shared between a [`Registration`] and its paired [`SetReadiness`], and
+/// temporarily owned by the intrusive MPSC queue while it is queued for
+/// delivery.
+#[derive(Debug)]
+struct ReadinessNode {
+    next_readiness: AtomicPtr<ReadinessNode>,
+    queued: AtomicBool,
+    alive: AtomicBool,
+    state: Mutex<Option<RegistrationState>>,
+}
+
+/// A lock-free MPSC queue of pending [`ReadinessNode`]s, one per [`Registry`].
+///
+/// Producers are any number of [`SetReadiness`] handles calling
+/// `set_readiness` from arbitrary threads. The single consumer is the
+/// registry's owning [`Poll`], which drains the queue on every call to
+/// [`Poll::poll`].
+///
+/// [`Poll`]: crate::Poll
+#[derive(Clone)]
+pub(crate) struct ReadinessQueue {
+    inner: Arc<ReadinessQueueInner>,
+}
+
+impl fmt::Debug for ReadinessQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadinessQueue").finish_non_exhaustive()
+    }
+}
+
+struct ReadinessQueueInner {
+    stub: Arc<ReadinessNode>,
+    head: AtomicPtr<ReadinessNode>,
+    tail: std::cell::UnsafeCell<*mut ReadinessNode>,
+    wake: Box<dyn Fn() + Send + Sync>,
+}
+
+// The queue itself only ever touches `tail` from the single consumer thread
+// (the thread calling `Poll::poll`), and `head` via atomic operations, so it
+// is safe to share across threads despite the raw pointers.
+unsafe impl Send for ReadinessQueueInner {}
+unsafe impl Sync for ReadinessQueueInner {}
+
+impl ReadinessQueue {
+    /// Create a new, empty readiness queue that calls `wake` every time a
+    /// node is newly enqueued, so that a [`Poll`](crate::Poll) blocked
+    /// waiting for readiness returns promptly instead of waiting out the
+    /// rest of its timeout.
+    pub(crate) fn new(wake: impl Fn() + Send + Sync + 'static) -> ReadinessQueue {
+        let stub = Arc::new(ReadinessNode {
+            next_readiness: AtomicPtr::new(ptr::null_mut()),
+            queued: AtomicBool::new(true),
+            alive: AtomicBool::new(false),
+            state: Mutex::new(None),
+        });
+        let stub_ptr = Arc::as_ptr(&stub) as *mut ReadinessNode;
+
+        ReadinessQueue {
+            inner: Arc::new(ReadinessQueueInner {
+                stub,
+                head: AtomicPtr::new(stub_ptr),
+                tail: std::cell::UnsafeCell::new(stub_ptr),
+                wake: Box::new(wake),
+            }),
+        }
+    }
+
+    /// Push `node` onto the queue and notify the waker. Safe to call from
+    /// any thread.
+    fn enqueue(&self, node: Arc<ReadinessNode>) {
+        // Hand our strong reference to the queue; it is reclaimed by the
+        // matching `Arc::from_raw` in `dequeue`.
+        let ptr = Arc::into_raw(node) as *mut ReadinessNode;
+        unsafe {
+            (*ptr).next_readiness.store(ptr::null_mut(), Ordering::Release);
+        }
+
+        let prev = self.inner.head.swap(ptr, Ordering::AcqRel);
+        unsafe {
+            (*prev).next_readiness.store(ptr, Ordering::Release);
+        }
+
+        (self.inner.wake)();
+    }
+
+    /// Pop the next node off the queue, if any.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called by the single consumer (the thread currently
+    /// inside `Poll::poll`).
+    fn dequeue(&self) -> Option<Arc<ReadinessNode>> {
+        unsafe {
+            let mut tail = *self.inner.tail.get();
+            let stub = Arc::as_ptr(&self.inner.stub) as *mut ReadinessNode;
+            let mut next = (*tail).next_readiness.load(Ordering::Acquire);
+
+            if tail == stub {
+                if next.is_null() {
+                    return None;
+                }
+                *self.inner.tail.get() = next;
+                tail = next;
+                next = (*next).next_readiness.load(Ordering::Acquire);
+            }
+
+            if !next.is_null() {
+                *self.inner.tail.get() = next;
+                return Some(Arc::from_raw(tail));
+            }
+
+            if tail != self.inner.head.load(Ordering::Acquire) {
+                // A push is in-flight: the producer has swapped `head` but
+                // has not yet linked `next_readiness` on the previous tail.
+                // Treat the queue as momentarily empty; the caller will try
+                // again on the next `poll`.
+                return None;
+            }
+
+            // Re-link the stub so the queue does not appear permanently
+            // empty while a concurrent push is landing.
+            self.enqueue(self.inner.stub.clone());
+
+            next = (*tail).next_readiness.load(Ordering::Acquire);
+            if !next.is_null() {
+                *self.inner.tail.get() = next;
+                return Some(Arc::from_raw(tail));
+            }
+
+            None
+        }
+    }
+
+    /// Drain every pending node, producing a synthetic `(Token, Interests)`
+    /// event for each one that is still alive and registered.
+    ///
+    /// Called by [`Poll::poll`](crate::Poll::poll) after the system selector
+    /// returns.
+    pub(crate) fn drain(&self) -> Vec<(Token, Interests)> {
+        let mut events = Vec::new();
+
+        while let Some(node) = self.dequeue() {
+            // Clear `queued` before reading readiness so that a
+            // `set_readiness` racing with this drain re-enqueues the node
+            // rather than being lost.
+            node.queued.store(false, Ordering::Release);
+
+            if !node.alive.load(Ordering::Acquire) {
+                continue;
+            }
+
+            let mut state = node.state.lock().unwrap();
+            if let Some(state) = &mut *state {
+                if let Some(readiness) = state.readiness.take() {
+                    // `Interests` has no empty representation and no
+                    // `BitAnd`, so the recorded readiness is delivered as
+                    // set, rather than masked against what the node was
+                    // registered for.
+                    events.push((state.token, readiness));
+                }
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bypasses `Registration`/`Registry::register` (which needs a real
+    // `Registry`) and builds a node directly, pre-bound to `queue`, the way
+    // `Registration::register` would leave it.
+    fn bound_node(queue: &ReadinessQueue, token: Token) -> Arc<ReadinessNode> {
+        Arc::new(ReadinessNode {
+            next_readiness: AtomicPtr::new(ptr::null_mut()),
+            queued: AtomicBool::new(false),
+            alive: AtomicBool::new(true),
+            state: Mutex::new(Some(RegistrationState {
+                queue: queue.clone(),
+                token,
+                readiness: None,
+            })),
+        })
+    }
+
+    fn set_readiness_on(node: &Arc<ReadinessNode>, queue: &ReadinessQueue, readiness: Interests) {
+        {
+            let mut state = node.state.lock().unwrap();
+            let state = state.as_mut().unwrap();
+            state.readiness = Some(match state.readiness {
+                Some(existing) => existing | readiness,
+                None => readiness,
+            });
+        }
+
+        if node
+            .queued
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            queue.enqueue(node.clone());
+        }
+    }
+
+    #[test]
+    fn enqueues_a_pending_readiness_change_at_most_once() {
+        let queue = ReadinessQueue::new(|| {});
+        let node = bound_node(&queue, Token(1));
+
+        // Two readiness changes before a drain: the first finds `queued`
+        // false and enqueues the node, the second must see it already
+        // queued and not enqueue it a second time.
+        set_readiness_on(&node, &queue, Interests::READABLE);
+        set_readiness_on(&node, &queue, Interests::WRITABLE);
+
+        let events = queue.drain();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, Token(1));
+        assert_eq!(events[0].1, Interests::READABLE | Interests::WRITABLE);
+
+        // Draining again produces nothing further until the node is
+        // re-armed.
+        assert!(queue.drain().is_empty());
+    }
+
+    #[test]
+    fn a_dead_node_is_skipped_instead_of_emitting_a_stale_event() {
+        let queue = ReadinessQueue::new(|| {});
+        let node = bound_node(&queue, Token(2));
+        set_readiness_on(&node, &queue, Interests::READABLE);
+
+        // Drop would normally do this; simulate it directly so the node
+        // stays enqueued.
+        node.alive.store(false, Ordering::Release);
+
+        let events = queue.drain();
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::event::Evented;
+use crate::{Events, Interests, Poll, Registry, Token};
+
+use std::collections::HashMap;
+use std::io;
+use std::time::Duration;
+
+/// An [`Evented`] value that turns raw readiness into typed events.
+///
+/// `Evented` only tells a loop *that* a token became readable or writable;
+/// it is up to the caller to know what that means for a particular handle
+/// and to turn it into something useful. `EventSource` does that
+/// translation: implementors declare the [`Interests`] they care about and a
+/// `process` method that consumes a readiness notification and emits zero or
+/// more `Event`s through a caller-supplied callback.
+///
+/// A TCP listener, for example, can emit one `Event` per connection accepted
+/// off of a single readable notification, without the caller needing to
+/// loop on `accept` itself.
+///
+/// `EventSource`s are meant to be driven by a [`Dispatcher`] rather than
+/// used directly.
+pub trait EventSource: Evented {
+    /// The event produced by this source.
+    type Event;
+
+    /// The readiness this source should be registered for.
+    fn interests(&self) -> Interests;
+
+    /// Turn a readiness notification into zero or more typed events.
+    ///
+    /// `emit` is called once per logical event the source wants to report
+    /// for this notification (e.g. once per accepted connection). A source
+    /// has no use for the `Data` a [`Dispatcher`] threads through to
+    /// callbacks, so it only ever hands `emit` the `Event` itself; the
+    /// `Dispatcher` is the one that pairs each `Event` with the outer
+    /// `data` before calling the user's callback.
+    fn process(&mut self, readiness: Interests, emit: &mut dyn FnMut(Self::Event));
+}
+
+/// An owning container that drives a collection of [`EventSource`]s.
+///
+/// A `Dispatcher` allocates a [`Token`] for each source it is given,
+/// registers it with a [`Poll`]'s [`Registry`], and calls back into user
+/// code as readiness events arrive. This gives callers a batteries-included
+/// reactor while leaving the lower level [`Evented`]/[`Registry`] API
+/// available underneath for handles that don't need typed events.
+pub struct Dispatcher<Data> {
+    poll: Poll,
+    next_token: usize,
+    sources: HashMap<Token, Box<dyn DispatchSource<Data>>>,
+}
+
+/// Object-safe view of an `EventSource` plus its callback, keyed by `Token`
+/// inside a [`Dispatcher`].
+trait DispatchSource<Data> {
+    fn evented(&self) -> &dyn Evented;
+    fn interests(&self) -> Interests;
+    fn process(&mut self, readiness: Interests, data: &mut Data);
+}
+
+struct Source<S: EventSource, F> {
+    source: S,
+    on_event: F,
+}
+
+impl<S, F, Data> DispatchSource<Data> for Source<S, F>
+where
+    S: EventSource,
+    F: FnMut(S::Event, &mut Data),
+{
+    fn evented(&self) -> &dyn Evented {
+        &self.source
+    }
+
+    fn interests(&self) -> Interests {
+        self.source.interests()
+    }
+
+    fn process(&mut self, readiness: Interests, data: &mut Data) {
+        let on_event = &mut self.on_event;
+        self.source
+            .process(readiness, &mut |event| on_event(event, &mut *data));
+    }
+}
+
+impl<Data> Dispatcher<Data> {
+    /// Create a new `Dispatcher` driving sources through `poll`.
+    pub fn new(poll: Poll) -> Dispatcher<Data> {
+        Dispatcher {
+            poll,
+            next_token: 0,
+            sources: HashMap::new(),
+        }
+    }
+
+    fn registry(&self) -> &Registry {
+        self.poll.registry()
+    }
+
+    /// Add `source` to the dispatcher, registering it with the underlying
+    /// [`Registry`] and routing every event it produces through `on_event`.
+    ///
+    /// Returns the [`Token`] allocated for `source`, which can be used with
+    /// [`Dispatcher::remove`].
+    pub fn add<S, F>(&mut self, source: S, on_event: F) -> io::Result<Token>
+    where
+        S: EventSource + 'static,
+        F: FnMut(S::Event, &mut Data) + 'static,
+    {
+        let token = Token(self.next_token);
+        self.next_token += 1;
+
+        let interests = source.interests();
+        let boxed: Box<dyn DispatchSource<Data>> = Box::new(Source { source, on_event });
+        boxed.evented().register(self.registry(), token, interests)?;
+        self.sources.insert(token, boxed);
+
+        Ok(token)
+    }
+
+    /// Stop driving the source registered at `token`, deregistering it from
+    /// the underlying [`Registry`].
+    pub fn remove(&mut self, token: Token) -> io::Result<()> {
+        match self.sources.remove(&token) {
+            Some(source) => source.evented().deregister(self.registry()),
+            None => Ok(()),
+        }
+    }
+
+    /// Poll for readiness events and dispatch each one to its source's
+    /// callback.
+    ///
+    /// This is equivalent to calling [`Poll::poll`] and then routing every
+    /// resulting event to the matching [`EventSource`].
+    pub fn dispatch(&mut self, timeout: Option<Duration>, data: &mut Data) -> io::Result<()> {
+        let mut events = Events::with_capacity(128);
+        self.poll.poll(&mut events, timeout)?;
+
+        for event in events.iter() {
+            if let Some(source) = self.sources.get_mut(&event.token()) {
+                source.process(event.interests(), data);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use std::num::NonZeroU8;
+use std::ops::BitOr;
+
+const READABLE: u8 = 0b0001;
+const WRITABLE: u8 = 0b0010;
+
+/// The readiness a handle may be registered for, or that an [`Event`]
+/// reports.
+///
+/// Backed by a `NonZeroU8` rather than a plain bitset so that `Interests`
+/// has no empty/all-zero representation: a handle always has to be
+/// registered for *something*, and readiness delivered through the
+/// user-space queue (see [`Registration`]) is always a real, non-empty set
+/// of bits rather than "nothing changed". This is also why `Interests` has
+/// no `BitAnd`: there is no way to mask two sets down to an empty one
+/// without a zero representation to hold the result.
+///
+/// [`Event`]: crate::event::Event
+/// [`Registration`]: crate::event::Registration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interests(NonZeroU8);
+
+impl Interests {
+    /// Interest in read readiness.
+    pub const READABLE: Interests = Interests(unsafe { NonZeroU8::new_unchecked(READABLE) });
+    /// Interest in write readiness.
+    pub const WRITABLE: Interests = Interests(unsafe { NonZeroU8::new_unchecked(WRITABLE) });
+
+    /// Whether this set includes read readiness.
+    pub fn is_readable(self) -> bool {
+        self.0.get() & READABLE != 0
+    }
+
+    /// Whether this set includes write readiness.
+    pub fn is_writable(self) -> bool {
+        self.0.get() & WRITABLE != 0
+    }
+}
+
+impl BitOr for Interests {
+    type Output = Interests;
+
+    fn bitor(self, other: Interests) -> Interests {
+        // Both operands are non-zero, so the result is too.
+        Interests(unsafe { NonZeroU8::new_unchecked(self.0.get() | other.0.get()) })
+    }
+}
@@ -0,0 +1,67 @@
+use crate::event::Event;
+use crate::{Events, Registry};
+
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Polls for readiness events raised against a [`Registry`].
+///
+/// `Poll` owns the top-level [`Registry`] handles are registered through;
+/// obtain it with [`Poll::registry`]. [`Poll::poll`] drains that registry's
+/// user-space readiness queue (populated through
+/// [`Registration`]/[`SetReadiness`](crate::event::SetReadiness)), blocking
+/// until something is pending or `timeout` elapses.
+///
+/// [`Registration`]: crate::event::Registration
+pub struct Poll {
+    registry: Registry,
+}
+
+impl Poll {
+    /// Create a new `Poll` instance, with its own `Registry`.
+    pub fn new() -> io::Result<Poll> {
+        Ok(Poll {
+            registry: Registry::new(),
+        })
+    }
+
+    /// Returns the `Registry` backing this `Poll`, used to register,
+    /// reregister and deregister `Evented` handles.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Block until at least one readiness event is available, or `timeout`
+    /// elapses, then fill `events` with every event that is ready.
+    ///
+    /// `events` is cleared at the start of every call.
+    pub fn poll(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        events.clear();
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut ready = self.registry.readiness_queue().drain();
+        // A wakeup can arrive with nothing left to drain (a stale flag or a
+        // spurious condvar wakeup), so keep waiting out the rest of the
+        // timeout until something is actually ready.
+        while ready.is_empty() {
+            let remaining = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break;
+                    }
+                    Some(deadline - now)
+                }
+                None => None,
+            };
+            self.registry.wait(remaining);
+            ready = self.registry.readiness_queue().drain();
+        }
+
+        for (token, interests) in ready {
+            events.push(Event::new(token, interests));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::event::{Evented, ReadinessQueue};
+use crate::{Interests, Token};
+
+use std::io;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+/// The entry point for registering [`Evented`] handles so their readiness
+/// is reported by a [`Poll`].
+///
+/// A `Registry` is backed by an `Arc`, so cloning it is always cheap and
+/// infallible: every clone shares the same readiness queue and wakeup
+/// condition as the `Registry` a [`Poll`] was created with. This is what
+/// lets [`Registry::register_owned`](crate::Registry::register_owned) keep a
+/// `Registry` handle around until drop time without duplicating any
+/// underlying system resource.
+///
+/// [`Poll`]: crate::Poll
+#[derive(Clone)]
+pub struct Registry {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    readiness_queue: ReadinessQueue,
+    wakeup: Arc<Wakeup>,
+}
+
+/// A `Condvar`-backed wakeup signal, shared between a `Registry`'s
+/// [`ReadinessQueue`] and the [`Poll`](crate::Poll) that blocks waiting for
+/// it.
+struct Wakeup {
+    woken: Mutex<bool>,
+    condvar: Condvar,
+}
+
+impl Wakeup {
+    fn new() -> Arc<Wakeup> {
+        Arc::new(Wakeup {
+            woken: Mutex::new(false),
+            condvar: Condvar::new(),
+        })
+    }
+
+    fn wake(&self) {
+        *self.woken.lock().unwrap() = true;
+        self.condvar.notify_one();
+    }
+
+    /// Block until woken or `timeout` elapses, clearing the wakeup flag
+    /// either way.
+    fn wait(&self, timeout: Option<Duration>) {
+        let mut woken = self.woken.lock().unwrap();
+        if !*woken {
+            woken = match timeout {
+                Some(timeout) => self.condvar.wait_timeout(woken, timeout).unwrap().0,
+                None => self.condvar.wait(woken).unwrap(),
+            };
+        }
+        *woken = false;
+    }
+}
+
+impl Registry {
+    /// Create a new `Registry` with its own readiness queue and wakeup
+    /// condition.
+    ///
+    /// Only [`Poll::new`](crate::Poll::new) constructs a top-level
+    /// `Registry`; every other handle to it is obtained by cloning.
+    pub(crate) fn new() -> Registry {
+        let wakeup = Wakeup::new();
+        let notify = wakeup.clone();
+        let readiness_queue = ReadinessQueue::new(move || notify.wake());
+
+        Registry {
+            inner: Arc::new(Inner {
+                readiness_queue,
+                wakeup,
+            }),
+        }
+    }
+
+    /// Register `handle` with this registry.
+    pub fn register<E: Evented + ?Sized>(
+        &self,
+        handle: &E,
+        token: Token,
+        interests: Interests,
+    ) -> io::Result<()> {
+        handle.register(self, token, interests)
+    }
+
+    /// Re-register `handle` with this registry.
+    pub fn reregister<E: Evented + ?Sized>(
+        &self,
+        handle: &E,
+        token: Token,
+        interests: Interests,
+    ) -> io::Result<()> {
+        handle.reregister(self, token, interests)
+    }
+
+    /// Deregister `handle` from this registry.
+    pub fn deregister<E: Evented + ?Sized>(&self, handle: &E) -> io::Result<()> {
+        handle.deregister(self)
+    }
+
+    /// Returns a cheaply cloneable handle to this registry's user-space
+    /// readiness queue, used by [`Registration`](crate::event::Registration)
+    /// to deliver events raised through a paired
+    /// [`SetReadiness`](crate::event::SetReadiness).
+    pub(crate) fn readiness_queue(&self) -> ReadinessQueue {
+        self.inner.readiness_queue.clone()
+    }
+
+    /// Block the calling thread until the readiness queue has something to
+    /// drain, or `timeout` elapses.
+    pub(crate) fn wait(&self, timeout: Option<Duration>) {
+        self.inner.wakeup.wait(timeout);
+    }
+}
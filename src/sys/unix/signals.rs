@@ -0,0 +1,188 @@
+//! `signalfd(2)`-backed delivery of Unix signals through the event loop.
+
+#![cfg(target_os = "linux")]
+
+use crate::unix::EventedFd;
+use crate::{Evented, Interests, Registry, Token};
+
+use std::io;
+use std::mem;
+use std::os::unix::io::RawFd;
+use std::ptr;
+
+/// A decoded signal read from a [`Signals`] source.
+#[derive(Debug, Clone, Copy)]
+pub struct Signal {
+    /// The signal number, e.g. `libc::SIGINT`.
+    pub signal: libc::c_int,
+    /// The pid of the process that sent the signal, if the kernel recorded
+    /// one.
+    pub pid: u32,
+    /// The uid of the process that sent the signal, if the kernel recorded
+    /// one.
+    pub uid: u32,
+}
+
+/// An [`Evented`] source that delivers Unix signals through a [`Poll`] loop
+/// instead of an async-signal handler.
+///
+/// Construction blocks the given signals in the calling thread's signal
+/// mask via `pthread_sigmask` and opens a `signalfd` registered as a
+/// readable system handle, which fits the existing [`EventedFd`]
+/// delegation model. Once registered and readable, [`Signals::pending`]
+/// decodes every `signalfd_siginfo` available without blocking.
+///
+/// # Invariants
+///
+/// The signals passed to `Signals` must remain blocked **process-wide**
+/// (not just on the thread that created the `Signals`) for the duration of
+/// its use. If any thread leaves them unblocked, the kernel may deliver a
+/// signal the normal way to that thread instead of queuing it for the
+/// `signalfd`, and delivery through the event loop becomes unreliable.
+///
+/// [`Poll`]: crate::Poll
+pub struct Signals {
+    fd: RawFd,
+    mask: libc::sigset_t,
+}
+
+impl Signals {
+    /// Create a `Signals` source delivering the given signal numbers.
+    pub fn new(signals: &[libc::c_int]) -> io::Result<Signals> {
+        let mask = mask_of(signals);
+        block_mask(&mask)?;
+        let fd = create_signalfd(&mask, -1)?;
+        Ok(Signals { fd, mask })
+    }
+
+    /// Start also delivering `signals` through this source.
+    pub fn add_signals(&mut self, signals: &[libc::c_int]) -> io::Result<()> {
+        for &signal in signals {
+            unsafe { libc::sigaddset(&mut self.mask, signal) };
+        }
+        block_mask(&self.mask)?;
+        self.fd = create_signalfd(&self.mask, self.fd)?;
+        Ok(())
+    }
+
+    /// Stop delivering `signals` through this source.
+    ///
+    /// The removed signals are also unblocked in the calling thread's
+    /// signal mask, so on that thread they return to being delivered (or
+    /// defaulted) the normal way instead of being silently swallowed:
+    /// dropping them from the `signalfd` mask without unblocking them would
+    /// leave them blocked there with nothing left reading them off the fd.
+    pub fn remove_signals(&mut self, signals: &[libc::c_int]) -> io::Result<()> {
+        for &signal in signals {
+            unsafe { libc::sigdelset(&mut self.mask, signal) };
+        }
+        unblock_mask(&mask_of(signals))?;
+        self.fd = create_signalfd(&self.mask, self.fd)?;
+        Ok(())
+    }
+
+    /// Decode every signal currently queued on the underlying `signalfd`,
+    /// without blocking.
+    ///
+    /// Should be called after a readiness notification for this source's
+    /// token; it is safe to call eagerly, it will simply return no signals
+    /// if none are pending.
+    pub fn pending(&self) -> io::Result<impl Iterator<Item = Signal>> {
+        let mut decoded = Vec::new();
+        let mut info: libc::signalfd_siginfo = unsafe { mem::zeroed() };
+        let info_size = mem::size_of::<libc::signalfd_siginfo>();
+
+        loop {
+            let n = unsafe {
+                libc::read(
+                    self.fd,
+                    &mut info as *mut _ as *mut libc::c_void,
+                    info_size,
+                )
+            };
+
+            if n == info_size as isize {
+                decoded.push(Signal {
+                    signal: info.ssi_signo as libc::c_int,
+                    pid: info.ssi_pid,
+                    uid: info.ssi_uid,
+                });
+                continue;
+            }
+
+            if n < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::WouldBlock {
+                    break;
+                }
+                return Err(err);
+            }
+
+            break;
+        }
+
+        Ok(decoded.into_iter())
+    }
+}
+
+fn mask_of(signals: &[libc::c_int]) -> libc::sigset_t {
+    unsafe {
+        let mut mask = mem::zeroed();
+        libc::sigemptyset(&mut mask);
+        for &signal in signals {
+            libc::sigaddset(&mut mask, signal);
+        }
+        mask
+    }
+}
+
+fn block_mask(mask: &libc::sigset_t) -> io::Result<()> {
+    let result = unsafe { libc::pthread_sigmask(libc::SIG_BLOCK, mask, ptr::null_mut()) };
+    if result != 0 {
+        return Err(io::Error::from_raw_os_error(result));
+    }
+    Ok(())
+}
+
+fn unblock_mask(mask: &libc::sigset_t) -> io::Result<()> {
+    let result = unsafe { libc::pthread_sigmask(libc::SIG_UNBLOCK, mask, ptr::null_mut()) };
+    if result != 0 {
+        return Err(io::Error::from_raw_os_error(result));
+    }
+    Ok(())
+}
+
+fn create_signalfd(mask: &libc::sigset_t, existing_fd: RawFd) -> io::Result<RawFd> {
+    let fd = unsafe { libc::signalfd(existing_fd, mask, libc::SFD_NONBLOCK | libc::SFD_CLOEXEC) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(fd)
+}
+
+impl Evented for Signals {
+    fn register(&self, registry: &Registry, token: Token, interests: Interests) -> io::Result<()> {
+        EventedFd(&self.fd).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &self,
+        registry: &Registry,
+        token: Token,
+        interests: Interests,
+    ) -> io::Result<()> {
+        EventedFd(&self.fd).reregister(registry, token, interests)
+    }
+
+    fn deregister(&self, registry: &Registry) -> io::Result<()> {
+        EventedFd(&self.fd).deregister(registry)
+    }
+}
+
+impl Drop for Signals {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+/// Associates an [`Evented`] handle with the [`Event`]s it produces.
+///
+/// A `Token` is chosen by the caller when registering a handle with a
+/// [`Registry`] and is handed back unchanged on every [`Event`] for that
+/// handle, so it is typically used as an index or key into whatever
+/// collection of handles the caller is driving.
+///
+/// [`Evented`]: crate::event::Evented
+/// [`Event`]: crate::event::Event
+/// [`Registry`]: crate::Registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Token(pub usize);